@@ -0,0 +1,45 @@
+use super::*;
+use crate::parser::{BinaryOperator, UnaryOperator};
+
+impl Binder {
+    /// Bind `expr [NOT] BETWEEN low AND high`, lowering it to
+    /// `expr >= low AND expr <= high` (optionally wrapped in `NOT`) over the
+    /// existing binary-op binding.
+    pub fn bind_between(
+        &mut self,
+        expr: &Expr,
+        negated: bool,
+        low: &Expr,
+        high: &Expr,
+    ) -> Result<BoundExpr, BindError> {
+        let ge = self.bind_binary_op(expr, &BinaryOperator::GtEq, low)?;
+        let le = self.bind_binary_op(expr, &BinaryOperator::LtEq, high)?;
+        let between = bind_and(ge, le);
+        Ok(if negated {
+            bind_not(between)
+        } else {
+            between
+        })
+    }
+}
+
+fn bind_and(left: BoundExpr, right: BoundExpr) -> BoundExpr {
+    BoundExpr {
+        kind: BoundExprKind::BinaryOp(BoundBinaryOp {
+            op: BinaryOperator::And,
+            left: Box::new(left),
+            right: Box::new(right),
+        }),
+        return_type: Some(DataType::Bool),
+    }
+}
+
+fn bind_not(expr: BoundExpr) -> BoundExpr {
+    BoundExpr {
+        kind: BoundExprKind::UnaryOp(BoundUnaryOp {
+            op: UnaryOperator::Not,
+            expr: Box::new(expr),
+        }),
+        return_type: Some(DataType::Bool),
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+use crate::parser::BinaryOperator;
+
+/// A bound `CASE WHEN ... THEN ... [ELSE ...] END` expression.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundCase {
+    pub when_then: Vec<(BoundExpr, BoundExpr)>,
+    pub else_expr: Option<Box<BoundExpr>>,
+}
+
+impl Binder {
+    /// Bind a `CASE` expression, unifying every `WHEN`/`THEN` and `ELSE`
+    /// branch's type into the expression's overall `return_type`.
+    pub fn bind_case(
+        &mut self,
+        operand: &Option<Box<Expr>>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: &Option<Box<Expr>>,
+    ) -> Result<BoundExpr, BindError> {
+        let mut when_then = Vec::with_capacity(conditions.len());
+        let mut return_type = None;
+        for (condition, result) in conditions.iter().zip(results.iter()) {
+            // `CASE x WHEN v THEN ...` is sugar for `CASE WHEN x = v THEN ...`,
+            // which already binds to a `Bool`; a searched `CASE WHEN <cond>`
+            // has no such guarantee, so check it explicitly.
+            let condition = match operand {
+                Some(operand) => self.bind_binary_op(operand, &BinaryOperator::Eq, condition)?,
+                None => {
+                    let condition = self.bind_expr(condition)?;
+                    check_boolean(&condition)?;
+                    condition
+                }
+            };
+            let result = self.bind_expr(result)?;
+            return_type = unify_branch_type(return_type, result.return_type)?;
+            when_then.push((condition, result));
+        }
+        let else_expr = else_result.as_deref().map(|e| self.bind_expr(e)).transpose()?;
+        if let Some(else_expr) = &else_expr {
+            return_type = unify_branch_type(return_type, else_expr.return_type)?;
+        }
+        Ok(BoundExpr {
+            kind: BoundExprKind::Case(BoundCase {
+                when_then,
+                else_expr: else_expr.map(Box::new),
+            }),
+            return_type,
+        })
+    }
+}
+
+/// Checks that a searched `CASE WHEN` condition is boolean-typed, the same
+/// way a comparison's operands are checked in `binary_op`. `NULL` is allowed
+/// through, same as elsewhere in the binder.
+fn check_boolean(expr: &BoundExpr) -> Result<(), BindError> {
+    match expr.return_type {
+        Some(DataType::Bool) | None => Ok(()),
+        Some(ty) => Err(BindError::ExpectedBoolean(ty)),
+    }
+}
+
+/// Folds one more branch's type into the running return type, promoting
+/// mixed `Int32`/`Float64` branches the same way `binary_op` already does
+/// for mixed-type binary expressions.
+fn unify_branch_type(
+    acc: Option<DataType>,
+    branch: Option<DataType>,
+) -> Result<Option<DataType>, BindError> {
+    Ok(match (acc, branch) {
+        (None, ty) | (ty, None) => ty,
+        (Some(a), Some(b)) if a == b => Some(a),
+        (Some(DataType::Int32), Some(DataType::Float64))
+        | (Some(DataType::Float64), Some(DataType::Int32)) => Some(DataType::Float64),
+        (Some(a), Some(b)) => return Err(BindError::TypeMismatch(a, b)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expr_of(ty: Option<DataType>) -> BoundExpr {
+        BoundExpr {
+            kind: BoundExprKind::Constant(DataValue::Null),
+            return_type: ty,
+        }
+    }
+
+    #[test]
+    fn check_boolean_rejects_non_bool_condition() {
+        assert!(check_boolean(&expr_of(Some(DataType::Bool))).is_ok());
+        assert!(check_boolean(&expr_of(None)).is_ok());
+        assert!(matches!(
+            check_boolean(&expr_of(Some(DataType::Int32))),
+            Err(BindError::ExpectedBoolean(DataType::Int32))
+        ));
+    }
+
+    #[test]
+    fn unify_branch_type_promotes_int_and_float() {
+        assert_eq!(
+            unify_branch_type(Some(DataType::Int32), Some(DataType::Float64)).unwrap(),
+            Some(DataType::Float64)
+        );
+        assert_eq!(
+            unify_branch_type(None, Some(DataType::String)).unwrap(),
+            Some(DataType::String)
+        );
+        assert!(unify_branch_type(Some(DataType::String), Some(DataType::Int32)).is_err());
+    }
+}
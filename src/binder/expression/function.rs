@@ -0,0 +1,60 @@
+use super::*;
+use crate::parser::{Function, FunctionArg, FunctionArgExpr};
+
+/// A bound scalar or aggregate function call.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundFunction {
+    pub name: String,
+    pub args: Vec<BoundExpr>,
+    pub return_type: DataType,
+}
+
+impl Binder {
+    /// Bind a function call, e.g. `count(*)`, `lower(name)`, `max(price)`.
+    pub fn bind_function(&mut self, function: &Function) -> Result<BoundExpr, BindError> {
+        let name = function.name.to_string().to_lowercase();
+        let args = function
+            .args
+            .iter()
+            .filter_map(arg_expr)
+            .map(|expr| self.bind_expr(expr))
+            .collect::<Result<Vec<_>, _>>()?;
+        let return_type = bind_return_type(&name, &args)?;
+        Ok(BoundExpr {
+            kind: BoundExprKind::Function(BoundFunction {
+                name,
+                args,
+                return_type,
+            }),
+            return_type: Some(return_type),
+        })
+    }
+}
+
+/// Extracts the inner `Expr` from a function argument, skipping bare `*`
+/// (e.g. `COUNT(*)`) which carries no expression to bind.
+fn arg_expr(arg: &FunctionArg) -> Option<&Expr> {
+    match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => Some(e),
+        FunctionArg::Named {
+            arg: FunctionArgExpr::Expr(e),
+            ..
+        } => Some(e),
+        _ => None,
+    }
+}
+
+/// Resolves a function's return type from its (lowercased) name and already
+/// bound arguments. Unknown functions are a bind error rather than a panic.
+fn bind_return_type(name: &str, args: &[BoundExpr]) -> Result<DataType, BindError> {
+    match name {
+        "count" => Ok(DataType::Int32),
+        "sum" | "min" | "max" => args
+            .first()
+            .and_then(|arg| arg.return_type)
+            .ok_or_else(|| BindError::UnknownFunction(name.to_owned())),
+        "avg" => Ok(DataType::Float64),
+        "lower" | "upper" | "trim" => Ok(DataType::String),
+        _ => Err(BindError::UnknownFunction(name.to_owned())),
+    }
+}
@@ -0,0 +1,41 @@
+use super::*;
+use crate::parser::BinaryOperator;
+
+/// A bound `expr [NOT] IN (list...)` expression.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundInList {
+    pub expr: Box<BoundExpr>,
+    pub list: Vec<BoundExpr>,
+    pub negated: bool,
+}
+
+impl Binder {
+    /// Bind `expr [NOT] IN (list...)`.
+    pub fn bind_in_list(
+        &mut self,
+        expr: &Expr,
+        list: &[Expr],
+        negated: bool,
+    ) -> Result<BoundExpr, BindError> {
+        let bound_expr = self.bind_expr(expr)?;
+        let list = list
+            .iter()
+            .map(|item| {
+                // Reuse `bind_binary_op`'s type checking so a list item whose
+                // type can't be compared against `expr` is rejected here,
+                // rather than binding successfully and misbehaving later in
+                // the executor.
+                self.bind_binary_op(expr, &BinaryOperator::Eq, item)?;
+                self.bind_expr(item)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BoundExpr {
+            kind: BoundExprKind::InList(BoundInList {
+                expr: Box::new(bound_expr),
+                list,
+                negated,
+            }),
+            return_type: Some(DataType::Bool),
+        })
+    }
+}
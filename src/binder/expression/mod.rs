@@ -3,13 +3,20 @@ use crate::catalog::ColumnRefId;
 use crate::parser::{Expr, Value};
 use crate::types::{DataType, DataValue};
 
+mod between;
 mod binary_op;
+mod case;
 mod column_ref;
+mod function;
+mod in_list;
 mod type_cast;
 mod unary_op;
 
 pub use self::binary_op::*;
+pub use self::case::*;
 pub use self::column_ref::*;
+pub use self::function::*;
+pub use self::in_list::*;
 pub use self::type_cast::*;
 pub use self::unary_op::*;
 
@@ -31,6 +38,9 @@ pub enum BoundExprKind {
     BinaryOp(BoundBinaryOp),
     UnaryOp(BoundUnaryOp),
     TypeCast(BoundTypeCast),
+    Case(BoundCase),
+    InList(BoundInList),
+    Function(BoundFunction),
 }
 
 impl BoundExpr {
@@ -54,6 +64,24 @@ impl Binder {
             Expr::UnaryOp { op, expr } => self.bind_unary_op(op, expr),
             Expr::Nested(expr) => self.bind_expr(expr),
             Expr::Cast { expr, data_type } => self.bind_type_cast(expr, data_type.clone()),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.bind_case(operand, conditions, results, else_result),
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => self.bind_between(expr, *negated, low, high),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => self.bind_in_list(expr, list, *negated),
+            Expr::Function(function) => self.bind_function(function),
             _ => todo!("bind expression: {:?}", expr),
         }
     }
@@ -0,0 +1,264 @@
+use super::*;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+/// A bound `CAST` expression.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoundTypeCast {
+    pub expr: Box<BoundExpr>,
+    pub ty: DataType,
+    /// How the underlying value is converted into `ty`.
+    pub conversion: Conversion,
+}
+
+/// How a raw value is turned into one of the engine's [`DataValue`]s.
+///
+/// This is shared by `CAST` and CSV ingestion: both need to turn a loosely-typed
+/// string (or an already-typed literal) into a concrete [`DataValue`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Conversion {
+    /// The source is already the right shape; copy it through unchanged.
+    Bytes,
+    String,
+    Integer,
+    Float,
+    Boolean,
+    /// Parse a calendar date in `YYYY-MM-DD` form.
+    Date,
+    /// Parse using the default RFC3339 / `YYYY-MM-DD HH:MM:SS` timestamp formats.
+    Timestamp,
+    /// Like `Timestamp`, but tags the result `DataValue::TimestampTz` instead
+    /// of `DataValue::Timestamp`.
+    TimestampTz,
+    /// Parse a naive timestamp using an explicit strftime-style format string.
+    TimestampFmt(String),
+    /// Parse a timestamp with an explicit format, then resolve its zone offset
+    /// and normalize the result to UTC.
+    TimestampTZFmt(String),
+}
+
+impl Conversion {
+    /// Applies this conversion to a string value, producing a [`DataValue`].
+    pub fn convert(&self, s: &str) -> Result<DataValue, BindError> {
+        Ok(match self {
+            Self::Bytes | Self::String => DataValue::String(s.to_owned()),
+            Self::Integer => DataValue::Int32(
+                s.parse()
+                    .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::Int32))?,
+            ),
+            Self::Float => DataValue::Float64(
+                s.parse()
+                    .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::Float64))?,
+            ),
+            Self::Boolean => DataValue::Bool(
+                s.parse()
+                    .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::Bool))?,
+            ),
+            Self::Date => DataValue::Date(parse_default_date(s)?),
+            Self::Timestamp => {
+                DataValue::Timestamp(parse_default_timestamp(s, DataType::Timestamp)?)
+            }
+            Self::TimestampTz => {
+                DataValue::TimestampTz(parse_default_timestamp(s, DataType::TimestampTz)?)
+            }
+            Self::TimestampFmt(fmt) => DataValue::Timestamp(parse_timestamp_with_format(s, fmt)?),
+            Self::TimestampTZFmt(fmt) => {
+                DataValue::TimestampTz(parse_timestamp_tz_with_format(s, fmt)?)
+            }
+        })
+    }
+}
+
+/// Parses `s` as an absolute time, trying RFC3339 first and then a naive
+/// `YYYY-MM-DD HH:MM:SS` format, returning microseconds since the Unix epoch.
+/// `ty` is only used to tag a parse failure with the right `DataType`.
+fn parse_default_timestamp(s: &str, ty: DataType) -> Result<i64, BindError> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.timestamp_micros());
+    }
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+        .map(|dt| dt.and_utc().timestamp_micros())
+        .map_err(|_| BindError::InvalidCast(s.to_owned(), ty))
+}
+
+/// Parses `s` against an explicit strftime-style `fmt` with no zone adjustment,
+/// returning microseconds since the Unix epoch.
+fn parse_timestamp_with_format(s: &str, fmt: &str) -> Result<i64, BindError> {
+    NaiveDateTime::parse_from_str(s, fmt)
+        .map(|dt| dt.and_utc().timestamp_micros())
+        .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::Timestamp))
+}
+
+/// Parses `s` against an explicit format that includes a zone offset, normalizes
+/// the result to UTC, and returns microseconds since the Unix epoch.
+fn parse_timestamp_tz_with_format(s: &str, fmt: &str) -> Result<i64, BindError> {
+    DateTime::parse_from_str(s, fmt)
+        .map(|dt| dt.with_timezone(&Utc).timestamp_micros())
+        .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::TimestampTz))
+}
+
+/// Parses `s` as a calendar date, returning microseconds since the Unix epoch
+/// at midnight UTC.
+fn parse_default_date(s: &str) -> Result<i64, BindError> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .and_then(|d| d.and_hms_opt(0, 0, 0).ok_or(chrono::ParseError::from(None)))
+        .map(|dt| dt.and_utc().timestamp_micros())
+        .map_err(|_| BindError::InvalidCast(s.to_owned(), DataType::Date))
+}
+
+impl Binder {
+    /// Bind a `CAST(expr AS data_type)` expression.
+    pub fn bind_type_cast(
+        &mut self,
+        expr: &Expr,
+        data_type: crate::parser::DataType,
+    ) -> Result<BoundExpr, BindError> {
+        let bound_expr = self.bind_expr(expr)?;
+        let ty = bind_data_type(&data_type)?;
+        let conversion = default_conversion(ty);
+
+        // Constant-fold casts over literals so downstream operators only ever
+        // see already-typed constants.
+        if let BoundExprKind::Constant(value) = &bound_expr.kind {
+            let folded = cast_value(value, ty, &conversion)?;
+            return Ok(BoundExpr::constant(folded));
+        }
+
+        Ok(BoundExpr {
+            kind: BoundExprKind::TypeCast(BoundTypeCast {
+                expr: Box::new(bound_expr),
+                ty,
+                conversion,
+            }),
+            return_type: Some(ty),
+        })
+    }
+}
+
+/// Resolves a parsed SQL type name to one of the engine's [`DataType`]s.
+fn bind_data_type(data_type: &crate::parser::DataType) -> Result<DataType, BindError> {
+    match data_type.to_string().to_uppercase().as_str() {
+        "INT" | "INTEGER" => Ok(DataType::Int32),
+        "FLOAT" | "DOUBLE" | "REAL" => Ok(DataType::Float64),
+        "BOOLEAN" | "BOOL" => Ok(DataType::Bool),
+        "DATE" => Ok(DataType::Date),
+        "TIMESTAMP" => Ok(DataType::Timestamp),
+        "TIMESTAMP WITH TIME ZONE" | "TIMESTAMPTZ" => Ok(DataType::TimestampTz),
+        s if s.starts_with("VARCHAR") || s.starts_with("CHAR") || s == "TEXT" || s == "STRING" => {
+            Ok(DataType::String)
+        }
+        _ => Err(BindError::UnsupportedType(data_type.to_string())),
+    }
+}
+
+/// The conversion a bare `CAST(... AS ty)` (no explicit format) uses for `ty`.
+fn default_conversion(ty: DataType) -> Conversion {
+    match ty {
+        DataType::Int32 => Conversion::Integer,
+        DataType::Float64 => Conversion::Float,
+        DataType::Bool => Conversion::Boolean,
+        DataType::String => Conversion::String,
+        DataType::Date => Conversion::Date,
+        DataType::Timestamp => Conversion::Timestamp,
+        DataType::TimestampTz => Conversion::TimestampTz,
+    }
+}
+
+fn cast_value(
+    value: &DataValue,
+    ty: DataType,
+    conversion: &Conversion,
+) -> Result<DataValue, BindError> {
+    if value == &DataValue::Null {
+        return Ok(DataValue::Null);
+    }
+    if value.data_type() == Some(ty) {
+        return Ok(value.clone());
+    }
+    match (value, ty) {
+        (DataValue::String(s), DataType::Date | DataType::Timestamp | DataType::TimestampTz) => {
+            conversion.convert(s)
+        }
+        (DataValue::Int32(n), DataType::Float64) => Ok(DataValue::Float64(*n as f64)),
+        (DataValue::Float64(f), DataType::Int32) => Ok(DataValue::Int32(*f as i32)),
+        _ => Err(BindError::InvalidCast(format!("{:?}", value), ty)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_conversion_for_date_is_not_timestamp() {
+        // A non-literal `CAST(col AS DATE)` must produce a `DataValue::Date`,
+        // not a `DataValue::Timestamp` with the same epoch micros, or it
+        // would never compare equal to a `Date` literal.
+        assert_eq!(default_conversion(DataType::Date), Conversion::Date);
+        assert_eq!(default_conversion(DataType::Timestamp), Conversion::Timestamp);
+    }
+
+    #[test]
+    fn default_conversion_for_timestamptz_is_not_timestamp() {
+        // Same bug, one type over: `CAST(col AS TIMESTAMPTZ)` must produce a
+        // `DataValue::TimestampTz`, not a `DataValue::Timestamp`, or it would
+        // never compare equal to a `TimestampTz` literal.
+        assert_eq!(
+            default_conversion(DataType::TimestampTz),
+            Conversion::TimestampTz
+        );
+    }
+
+    #[test]
+    fn date_conversion_produces_date_value() {
+        let value = Conversion::Date.convert("2024-03-05").unwrap();
+        match value {
+            DataValue::Date(_) => {}
+            other => panic!("expected DataValue::Date, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn timestamptz_conversion_produces_timestamptz_value() {
+        let value = Conversion::TimestampTz
+            .convert("2024-01-01T00:00:00+02:00")
+            .unwrap();
+        match value {
+            DataValue::TimestampTz(_) => {}
+            other => panic!("expected DataValue::TimestampTz, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn cast_value_on_non_literal_path_matches_literal_path() {
+        // `cast_value` is only reached for constants (non-constants build a
+        // `BoundTypeCast` instead), but `Conversion::convert` is what the
+        // executor runs for those `BoundTypeCast`s at runtime, so the two
+        // paths must agree on the resulting `DataValue` variant.
+        let literal = cast_value(
+            &DataValue::String("2024-03-05".into()),
+            DataType::Date,
+            &default_conversion(DataType::Date),
+        )
+        .unwrap();
+        let runtime = default_conversion(DataType::Date)
+            .convert("2024-03-05")
+            .unwrap();
+        assert_eq!(literal.data_type(), runtime.data_type());
+        assert_eq!(literal, runtime);
+    }
+
+    #[test]
+    fn cast_value_on_non_literal_path_matches_literal_path_for_timestamptz() {
+        let literal = cast_value(
+            &DataValue::String("2024-01-01T00:00:00+02:00".into()),
+            DataType::TimestampTz,
+            &default_conversion(DataType::TimestampTz),
+        )
+        .unwrap();
+        let runtime = default_conversion(DataType::TimestampTz)
+            .convert("2024-01-01T00:00:00+02:00")
+            .unwrap();
+        assert_eq!(literal.data_type(), runtime.data_type());
+        assert_eq!(literal, runtime);
+    }
+}
@@ -0,0 +1,15 @@
+//! An in-memory storage engine. Tables are plain `Vec<DataChunkRef>`s guarded
+//! by an `RwLock`. Table data itself doesn't survive a process restart, but
+//! it can be written out and reloaded via [`InMemoryTableInner::checkpoint`].
+//! See [`storage::rocksdb`](super::rocksdb) for the fully persistent engine.
+
+mod checkpoint;
+mod table;
+mod transaction;
+
+pub use table::{InMemoryTable, InMemoryTableInner, InMemoryTableInnerRef};
+pub use transaction::InMemoryTransaction;
+
+pub(super) use crate::catalog::ColumnCatalog;
+pub(super) use crate::storage::ColumnId;
+pub(super) use std::collections::HashMap;
@@ -4,7 +4,8 @@ use crate::catalog::{ColumnDesc, TableRefId};
 use crate::storage::Table;
 use async_trait::async_trait;
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::vec::Vec;
 
@@ -16,10 +17,22 @@ pub struct InMemoryTable {
     pub(super) inner: InMemoryTableInnerRef,
 }
 
+/// The commit-timestamp range a row is visible in: `[begin_ts, end_ts)`.
+/// A row deleted by no one yet has `end_ts == u64::MAX`.
+struct RowVersion {
+    begin_ts: u64,
+    end_ts: u64,
+}
+
 pub(super) struct InMemoryTableInner {
     chunks: Vec<DataChunkRef>,
-    deleted_rows: HashSet<usize>,
+    /// One entry per logical row, in the same order rows appear when
+    /// `chunks` is flattened, indexed by the `row_id` used elsewhere.
+    row_versions: Vec<RowVersion>,
     columns: HashMap<ColumnId, ColumnDesc>,
+    /// The last commit timestamp handed out. Timestamp `0` means "before any
+    /// transaction has ever run", so the first commit gets timestamp `1`.
+    last_ts: AtomicU64,
 }
 
 pub(super) type InMemoryTableInnerRef = Arc<RwLock<InMemoryTableInner>>;
@@ -28,23 +41,65 @@ impl InMemoryTableInner {
     pub fn new(columns: &[ColumnCatalog]) -> Self {
         Self {
             chunks: vec![],
+            row_versions: vec![],
             columns: columns
                 .iter()
                 .map(|col| (col.id(), col.desc().clone()))
                 .collect(),
-            deleted_rows: HashSet::new(),
+            last_ts: AtomicU64::new(0),
         }
     }
 
-    pub fn append(&mut self, chunk: DataChunk) -> Result<(), StorageError> {
+    /// The last commit timestamp handed out, used as a transaction's read
+    /// snapshot. Any commit started after this call is guaranteed to receive
+    /// a strictly greater timestamp, so rows it publishes stay invisible to
+    /// this snapshot.
+    pub fn snapshot_ts(&self) -> u64 {
+        self.last_ts.load(Ordering::SeqCst)
+    }
+
+    /// Allocates the next commit timestamp, strictly greater than any
+    /// timestamp previously returned by `snapshot_ts` or `next_commit_ts`.
+    pub fn next_commit_ts(&self) -> u64 {
+        self.last_ts.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Appends a chunk, stamping every one of its rows with `begin_ts` and
+    /// leaving them open (`end_ts == u64::MAX`) until they are deleted.
+    pub fn append(&mut self, chunk: DataChunk, begin_ts: u64) -> Result<(), StorageError> {
         // The BaseTable will not validate the datachunk, it is Binder's and Executor's task.
         // TODO(runji): check and reorder columns
+        let cardinality = chunk.cardinality();
+        self.row_versions
+            .extend((0..cardinality).map(|_| RowVersion {
+                begin_ts,
+                end_ts: u64::MAX,
+            }));
         self.chunks.push(Arc::new(chunk));
         Ok(())
     }
 
-    pub fn delete(&mut self, row_id: usize) -> Result<(), StorageError> {
-        self.deleted_rows.insert(row_id);
+    /// Returns an error if `row_id` was already closed out by another
+    /// transaction, i.e. a write-write conflict on the same logical row.
+    pub fn check_no_conflict(&self, row_id: usize) -> Result<(), StorageError> {
+        let version = self
+            .row_versions
+            .get(row_id)
+            .ok_or(StorageError::InvalidRow(row_id))?;
+        if version.end_ts != u64::MAX {
+            return Err(StorageError::WriteConflict(row_id));
+        }
+        Ok(())
+    }
+
+    /// Closes out `row_id` as of `end_ts`. Callers must have already checked
+    /// [`check_no_conflict`](Self::check_no_conflict).
+    pub fn delete(&mut self, row_id: usize, end_ts: u64) -> Result<(), StorageError> {
+        let version = self
+            .row_versions
+            .get_mut(row_id)
+            .ok_or(StorageError::InvalidRow(row_id))?;
+        version.end_ts = end_ts;
         Ok(())
     }
 
@@ -52,8 +107,15 @@ impl InMemoryTableInner {
         self.chunks.clone()
     }
 
-    pub fn get_all_deleted_rows(&self) -> HashSet<usize> {
-        self.deleted_rows.clone()
+    /// Rows not visible to a transaction reading at `snapshot_ts`: either
+    /// already closed out as of `snapshot_ts`, or appended after it.
+    pub fn invisible_rows(&self, snapshot_ts: u64) -> HashSet<usize> {
+        self.row_versions
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !(v.begin_ts <= snapshot_ts && snapshot_ts < v.end_ts))
+            .map(|(row_id, _)| row_id)
+            .collect()
     }
 
     fn column_descs(&self, ids: &[ColumnId]) -> StorageResult<Vec<ColumnDesc>> {
@@ -66,14 +128,53 @@ impl InMemoryTableInner {
             })
             .try_collect()
     }
+
+    /// Builds a table whose chunks are restored from a checkpoint written by
+    /// [`checkpoint`](Self::checkpoint), with every restored row visible from
+    /// the start (`begin_ts == 0`).
+    pub fn restore(columns: &[ColumnCatalog], path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let chunks = Self::restore_checkpoint(path)?;
+        let row_versions = chunks
+            .iter()
+            .flat_map(|chunk| {
+                (0..chunk.cardinality()).map(|_| RowVersion {
+                    begin_ts: 0,
+                    end_ts: u64::MAX,
+                })
+            })
+            .collect();
+        Ok(Self {
+            chunks,
+            row_versions,
+            columns: columns
+                .iter()
+                .map(|col| (col.id(), col.desc().clone()))
+                .collect(),
+            last_ts: AtomicU64::new(0),
+        })
+    }
 }
 
 impl InMemoryTable {
-    pub fn new(table_ref_id: TableRefId, columns: &[ColumnCatalog]) -> Self {
-        Self {
+    /// Builds a table for `table_ref_id`, restoring its chunks from
+    /// `checkpoint_path` if a checkpoint already exists there (a restart
+    /// picking back up a table that was checkpointed before going down), or
+    /// starting empty otherwise (the table's first-ever creation). This is
+    /// the only constructor, so table startup can't accidentally skip
+    /// restoration by going through a separate "fresh" path.
+    pub fn open(
+        table_ref_id: TableRefId,
+        columns: &[ColumnCatalog],
+        checkpoint_path: Option<&Path>,
+    ) -> StorageResult<Self> {
+        let inner = match checkpoint_path {
+            Some(path) if path.exists() => InMemoryTableInner::restore(columns, path)?,
+            _ => InMemoryTableInner::new(columns),
+        };
+        Ok(Self {
             table_ref_id,
-            inner: Arc::new(RwLock::new(InMemoryTableInner::new(columns))),
-        }
+            inner: Arc::new(RwLock::new(inner)),
+        })
     }
 }
 
@@ -101,4 +202,27 @@ impl Table for InMemoryTable {
     async fn update(&self) -> StorageResult<Self::TransactionType> {
         Ok(InMemoryTransaction::start(self)?)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_timestamps_exceed_earlier_snapshots() {
+        let inner = InMemoryTableInner::new(&[]);
+
+        // A reader snapshotting now must never see a commit that starts
+        // after this point: the commit timestamp has to be strictly
+        // greater than any snapshot timestamp already handed out.
+        let read_ts = inner.snapshot_ts();
+        let commit_ts = inner.next_commit_ts();
+        assert!(commit_ts > read_ts);
+
+        // Repeated allocations keep advancing past every prior snapshot.
+        let read_ts2 = inner.snapshot_ts();
+        assert_eq!(read_ts2, commit_ts);
+        let commit_ts2 = inner.next_commit_ts();
+        assert!(commit_ts2 > read_ts2);
+    }
+}
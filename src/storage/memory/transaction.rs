@@ -0,0 +1,78 @@
+use super::*;
+use crate::array::{DataChunk, DataChunkRef};
+use crate::storage::{StorageResult, Transaction};
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+/// A snapshot-isolated transaction against an [`InMemoryTable`].
+///
+/// Appends and deletes are buffered locally and only applied to the shared
+/// table state on [`commit`](Self::commit), under a single acquisition of
+/// the table's `RwLock`, so a `write()`/`update()` either publishes all of
+/// its changes atomically or none of them. Reads always see the snapshot as
+/// of `read_ts`, taken when the transaction started.
+pub struct InMemoryTransaction {
+    table: InMemoryTable,
+    read_ts: u64,
+    appends: Vec<DataChunk>,
+    deletes: Vec<usize>,
+}
+
+impl InMemoryTransaction {
+    pub(super) fn start(table: &InMemoryTable) -> StorageResult<Self> {
+        let read_ts = table.inner.read().unwrap().snapshot_ts();
+        Ok(Self {
+            table: table.clone(),
+            read_ts,
+            appends: vec![],
+            deletes: vec![],
+        })
+    }
+
+    pub fn append(&mut self, chunk: DataChunk) -> StorageResult<()> {
+        self.appends.push(chunk);
+        Ok(())
+    }
+
+    pub fn delete(&mut self, row_id: usize) -> StorageResult<()> {
+        self.deletes.push(row_id);
+        Ok(())
+    }
+
+    pub fn scan(&self) -> StorageResult<Vec<DataChunkRef>> {
+        Ok(self.table.inner.read().unwrap().get_all_chunks())
+    }
+
+    /// Rows not visible to this transaction's snapshot: those deleted as of
+    /// `read_ts` and those appended after it.
+    pub fn deleted_rows(&self) -> StorageResult<HashSet<usize>> {
+        Ok(self.table.inner.read().unwrap().invisible_rows(self.read_ts))
+    }
+}
+
+#[async_trait]
+impl Transaction for InMemoryTransaction {
+    async fn commit(self) -> StorageResult<()> {
+        if self.appends.is_empty() && self.deletes.is_empty() {
+            return Ok(());
+        }
+        let mut inner = self.table.inner.write().unwrap();
+        for &row_id in &self.deletes {
+            inner.check_no_conflict(row_id)?;
+        }
+        let commit_ts = inner.next_commit_ts();
+        for chunk in self.appends {
+            inner.append(chunk, commit_ts)?;
+        }
+        for row_id in self.deletes {
+            inner.delete(row_id, commit_ts)?;
+        }
+        Ok(())
+    }
+
+    async fn abort(self) -> StorageResult<()> {
+        // Nothing was published to the shared table state yet, so dropping
+        // the buffered appends/deletes is enough to roll back.
+        Ok(())
+    }
+}
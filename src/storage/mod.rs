@@ -0,0 +1,68 @@
+//! Storage engines and the `Table`/`Transaction` traits they implement.
+//!
+//! The rest of the system (binder, executor) only ever talks to a `Table`
+//! through this trait, so a table's data can live in memory or on disk
+//! without the surrounding engine caring which.
+
+pub mod memory;
+pub mod rocksdb;
+
+use crate::catalog::{ColumnDesc, TableRefId};
+use async_trait::async_trait;
+use std::result::Result;
+use thiserror::Error;
+
+pub type ColumnId = u32;
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// A table backed by some storage engine.
+#[async_trait]
+pub trait Table: Clone + Send + Sync + 'static {
+    type TransactionType: Transaction;
+
+    fn column_descs(&self, ids: &[ColumnId]) -> StorageResult<Vec<ColumnDesc>>;
+
+    fn table_id(&self) -> TableRefId;
+
+    /// Starts a transaction for appending and deleting rows.
+    async fn write(&self) -> StorageResult<Self::TransactionType>;
+
+    /// Starts a transaction for scanning rows.
+    async fn read(&self) -> StorageResult<Self::TransactionType>;
+
+    /// Starts a transaction that may both scan and mutate rows.
+    async fn update(&self) -> StorageResult<Self::TransactionType>;
+}
+
+/// A transaction against a single table.
+#[async_trait]
+pub trait Transaction: Sized + Send + Sync {
+    async fn commit(self) -> StorageResult<()>;
+
+    async fn abort(self) -> StorageResult<()>;
+}
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("invalid column id: {0}")]
+    InvalidColumn(ColumnId),
+
+    #[error("invalid row id: {0}")]
+    InvalidRow(usize),
+
+    #[error("write-write conflict on row {0}")]
+    WriteConflict(usize),
+
+    #[error("key-value store error: {0}")]
+    Kv(#[from] ::rocksdb::Error),
+
+    #[error("serialization error: {0}")]
+    Serialize(#[from] bincode::Error),
+
+    #[error("checkpoint io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("corrupt checkpoint file: {0}")]
+    CorruptCheckpoint(String),
+}
@@ -0,0 +1,58 @@
+//! An embedded, persistent storage engine backed by a RocksDB-style LSM tree.
+//!
+//! It implements the same [`Table`](super::Table)/[`Transaction`](super::Transaction)
+//! interface as [`storage::memory`](super::memory), so tables can be backed by
+//! this engine without the rest of the system noticing. Each table's chunks
+//! are stored as `(table_ref_id, chunk_seq) -> serialized DataChunk` entries,
+//! and deletes are tombstone keys rather than an in-memory set, so both
+//! survive a process restart.
+
+mod table;
+mod transaction;
+
+pub use table::{RocksDBTable, RocksDBTableInner};
+pub use transaction::RocksDBTransaction;
+
+use crate::catalog::TableRefId;
+
+const CHUNK_PREFIX: &str = "chunk";
+const TOMBSTONE_PREFIX: &str = "tombstone";
+
+/// The key under which chunk `chunk_seq` of `table_ref_id` is stored.
+fn chunk_key(table_ref_id: TableRefId, chunk_seq: u32) -> Vec<u8> {
+    format!("{}/{:?}/{:010}", CHUNK_PREFIX, table_ref_id, chunk_seq).into_bytes()
+}
+
+/// The key prefix shared by every chunk of `table_ref_id`, for prefix scans.
+fn chunk_prefix(table_ref_id: TableRefId) -> Vec<u8> {
+    format!("{}/{:?}/", CHUNK_PREFIX, table_ref_id).into_bytes()
+}
+
+/// The tombstone key recording that `row_id` of `table_ref_id` was deleted.
+fn tombstone_key(table_ref_id: TableRefId, row_id: usize) -> Vec<u8> {
+    format!(
+        "{}/{:?}/{:020}",
+        TOMBSTONE_PREFIX, table_ref_id, row_id
+    )
+    .into_bytes()
+}
+
+/// The key prefix shared by every tombstone of `table_ref_id`, for prefix scans.
+fn tombstone_prefix(table_ref_id: TableRefId) -> Vec<u8> {
+    format!("{}/{:?}/", TOMBSTONE_PREFIX, table_ref_id).into_bytes()
+}
+
+/// Parses a row id back out of a tombstone key produced by [`tombstone_key`].
+fn parse_tombstone_row_id(table_ref_id: TableRefId, key: &[u8]) -> Option<usize> {
+    let key = std::str::from_utf8(key).ok()?;
+    let suffix = key.strip_prefix(&String::from_utf8(tombstone_prefix(table_ref_id)).ok()?)?;
+    suffix.parse().ok()
+}
+
+/// Parses a chunk sequence number back out of a chunk key produced by
+/// [`chunk_key`].
+fn parse_chunk_seq(table_ref_id: TableRefId, key: &[u8]) -> Option<u32> {
+    let key = std::str::from_utf8(key).ok()?;
+    let suffix = key.strip_prefix(&String::from_utf8(chunk_prefix(table_ref_id)).ok()?)?;
+    suffix.parse().ok()
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::array::{DataChunk, DataChunkRef};
+use crate::storage::{StorageResult, Transaction};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+
+/// A transaction against a [`RocksDBTable`](super::RocksDBTable).
+///
+/// Appends and deletes are buffered locally and only written to the
+/// key-value store on [`commit`](Self::commit), inside a single RocksDB
+/// transaction, so that a `write()`/`update()` either persists all of its
+/// changes atomically or none of them.
+pub struct RocksDBTransaction {
+    table: RocksDBTable,
+    appends: Vec<DataChunk>,
+    deletes: Vec<usize>,
+}
+
+impl RocksDBTransaction {
+    pub(super) fn start(table: &RocksDBTable) -> StorageResult<Self> {
+        Ok(Self {
+            table: table.clone(),
+            appends: vec![],
+            deletes: vec![],
+        })
+    }
+
+    /// Buffers a chunk to be persisted on commit.
+    pub fn append(&mut self, chunk: DataChunk) -> StorageResult<()> {
+        self.appends.push(chunk);
+        Ok(())
+    }
+
+    /// Buffers a tombstone for `row_id` to be persisted on commit.
+    pub fn delete(&mut self, row_id: usize) -> StorageResult<()> {
+        self.deletes.push(row_id);
+        Ok(())
+    }
+
+    /// Returns every chunk already durable in the store, plus any appended
+    /// earlier in this same transaction.
+    pub fn scan(&self) -> StorageResult<Vec<DataChunkRef>> {
+        let mut chunks = self.table.inner.scan()?;
+        chunks.extend(self.appends.iter().cloned().map(std::sync::Arc::new));
+        Ok(chunks)
+    }
+
+    /// Returns every row id tombstoned in the store, plus any deleted earlier
+    /// in this same transaction.
+    pub fn deleted_rows(&self) -> StorageResult<HashSet<usize>> {
+        let mut rows = self.table.inner.deleted_rows()?;
+        rows.extend(self.deletes.iter().copied());
+        Ok(rows)
+    }
+}
+
+#[async_trait]
+impl Transaction for RocksDBTransaction {
+    async fn commit(self) -> StorageResult<()> {
+        let db_txn = self.table.inner.db.transaction();
+        for chunk in &self.appends {
+            let seq = self
+                .table
+                .inner
+                .next_chunk_seq
+                .fetch_add(1, Ordering::SeqCst);
+            let key = chunk_key(self.table.table_ref_id, seq);
+            let value = bincode::serialize(chunk)?;
+            db_txn.put(key, value)?;
+        }
+        for row_id in &self.deletes {
+            let key = tombstone_key(self.table.table_ref_id, *row_id);
+            db_txn.put(key, [])?;
+        }
+        db_txn.commit()?;
+        Ok(())
+    }
+
+    async fn abort(self) -> StorageResult<()> {
+        // Buffered appends/deletes were never written to the store, so
+        // dropping them is enough to roll back.
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+//! The scalar type system shared by the binder, executor, and storage layers.
+
+/// A SQL data type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DataType {
+    Int32,
+    Float64,
+    String,
+    Bool,
+    /// A calendar date with no time-of-day component.
+    Date,
+    /// A timestamp with no associated time zone.
+    Timestamp,
+    /// A timestamp normalized to UTC at storage time.
+    TimestampTz,
+}
+
+/// A runtime scalar value.
+///
+/// `Date`, `Timestamp`, and `TimestampTz` all store microseconds since the Unix
+/// epoch so that ordering and equality are plain integer comparisons regardless
+/// of which of the three types is in play.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DataValue {
+    Int32(i32),
+    Float64(f64),
+    String(String),
+    Bool(bool),
+    /// Microseconds since the Unix epoch, truncated to midnight.
+    Date(i64),
+    /// Microseconds since the Unix epoch.
+    Timestamp(i64),
+    /// Microseconds since the Unix epoch, already normalized to UTC.
+    TimestampTz(i64),
+    Null,
+}
+
+impl DataValue {
+    /// Returns the type of this value, or `None` if it is `NULL`.
+    pub fn data_type(&self) -> Option<DataType> {
+        Some(match self {
+            Self::Int32(_) => DataType::Int32,
+            Self::Float64(_) => DataType::Float64,
+            Self::String(_) => DataType::String,
+            Self::Bool(_) => DataType::Bool,
+            Self::Date(_) => DataType::Date,
+            Self::Timestamp(_) => DataType::Timestamp,
+            Self::TimestampTz(_) => DataType::TimestampTz,
+            Self::Null => return None,
+        })
+    }
+}